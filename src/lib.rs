@@ -1,8 +1,14 @@
 #[allow(warnings)]
 mod bindings;
+use std::collections::HashMap;
+
 use regex::Regex;
 use serde_json::Value as JsonValue;
 
+// `http::post` and `time::sleep` (used by the retry/backoff path below) come from the same
+// `supabase:wrappers::{http, time}` guest world already relied on elsewhere in this file for
+// `http::get` and `time::parse_from_str` - this tree doesn't vendor the WIT definitions, so
+// that's as far as this can be confirmed without the host crate to check against
 use bindings::{
     exports::supabase::wrappers::routines::Guest,
     supabase::wrappers::{
@@ -15,46 +21,670 @@ use bindings::{
 #[derive(Debug, Default)]
 struct ExampleFdw {
     base_url: String,
+    // server-level credentials for private/OAuth-protected sheets; any of these may be backed
+    // by a Supabase Vault secret via the options API's existing `<name>_id` indirection.
+    // `api_key` only unlocks Google's public-data quota (sent as a `key=` query param);
+    // reading or writing a private sheet needs a real OAuth `bearer_token`
+    api_key: Option<String>,
+    bearer_token: Option<String>,
+    cookie: Option<String>,
+    // retry tuning for transient HTTP failures, configurable via server options
+    max_retries: u32,
+    retry_base_delay_ms: u64,
     src_rows: Vec<JsonValue>,
     src_idx: usize,
+    // per-target-column source binding, populated from the `columns` table option
+    col_mappings: HashMap<String, ColumnMapping>,
+    // quals the `tq` pushdown couldn't express, applied locally in iter_scan
+    local_quals: Vec<LocalQual>,
+
+    // write-back state, populated in begin_modify and flushed in end_modify
+    api_base_url: String,
+    modify_sheet_id: String,
+    modify_range: String,
+    modify_sheet_gid: i64,
+    pending_inserts: Vec<Vec<JsonValue>>,
+    // row number paired with its bound (sheet_col_idx, value) cells - sparse, so an UPDATE
+    // never touches a sheet column the foreign table doesn't expose
+    pending_updates: Vec<(i64, Vec<(usize, JsonValue)>)>,
+    pending_deletes: Vec<i64>,
+}
+
+// the `rowid_column` always refers to a 1-based data-row number (excluding the header), so
+// the corresponding A1 row is offset by one header row
+const HEADER_OFFSET: i64 = 1;
+
+#[derive(Debug, Clone)]
+struct LocalQual {
+    field: String,
+    operator: String,
+    value: Cell,
+}
+
+#[derive(Debug, Clone)]
+struct ColumnMapping {
+    // 0-based index into a gviz row's `c` array
+    src_idx: usize,
+    // declared cell type, e.g. "f64", "bool", "timestamp_iso"
+    col_type: String,
 }
-fn parse_date_from_interface(src: &str) -> Option<Cell> {
-    use regex::Regex;
 
-    let re = Regex::new(r"Date\((\d{4}),(\d{1,2}),(\d{1,2})\)").unwrap();
-    if let Some(caps) = re.captures(src) {
-        // Extract year, month, and day values
-        let year: i32 = caps[1].parse().ok()?;
-        let month_str = &caps[2];
-        let day_str = &caps[3];
+// parse any of the gviz temporal encodings for a `v` cell value:
+//   Date(year,month,day)                     -> Cell::Date (month is 0-based, day is not)
+//   Date(year,month,day,hours,minutes,secs)  -> Cell::Timestamp/Timestamptz
+//   [hours,minutes,seconds,millis]           -> time-of-day, always Cell::String (see
+//                                                parse_gviz_time_array for why)
+// the target column's type_oid picks Date vs Timestamp vs Timestamptz for the first two forms
+fn parse_gviz_temporal(src: &JsonValue, type_oid: TypeOid) -> Option<Cell> {
+    if let Some(s) = src.as_str() {
+        return parse_gviz_date_string(s, type_oid);
+    }
+    if let Some(arr) = src.as_array() {
+        return parse_gviz_time_array(arr);
+    }
+    None
+}
 
-        // Debug output to check what is captured
-        println!("Captured year: {}, month: {}, day: {}", year, month_str, day_str);
+fn parse_gviz_date_string(s: &str, type_oid: TypeOid) -> Option<Cell> {
+    let re = Regex::new(
+        r"^Date\((\d{4}),(\d{1,2}),(\d{1,2})(?:,(\d{1,2}),(\d{1,2}),(\d{1,2}))?\)$",
+    )
+    .unwrap();
+    let caps = re.captures(s)?;
 
-                // Safely parse month and day
-        let month: u32 = month_str.parse::<u32>().ok()? + 1; // Adjust 0-based month
-        let day: u32 = day_str.parse::<u32>().ok()? + 1; // Adjust 0-based day
+    let year: i32 = caps[1].parse().ok()?;
+    let month: u32 = caps[2].parse::<u32>().ok()? + 1; // gviz months are 0-based
+    let day: u32 = caps[3].parse().ok()?; // gviz days are NOT 0-based
+
+    match (caps.get(4), caps.get(5), caps.get(6)) {
+        (Some(h), Some(mi), Some(se)) => {
+            let formatted = format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                year,
+                month,
+                day,
+                h.as_str().parse::<u32>().ok()?,
+                mi.as_str().parse::<u32>().ok()?,
+                se.as_str().parse::<u32>().ok()?,
+            );
+            let epoch_micros = time::parse_from_str(&formatted, "%Y-%m-%dT%H:%M:%S").ok()?;
+            Some(match type_oid {
+                TypeOid::Timestamptz => Cell::Timestamptz(epoch_micros),
+                _ => Cell::Timestamp(epoch_micros),
+            })
+        }
+        _ => {
+            let formatted = format!("{:04}-{:02}-{:02}", year, month, day);
+            let epoch_micros = time::parse_from_str(&formatted, "%Y-%m-%d").ok()?;
+            Some(match type_oid {
+                TypeOid::Timestamp => Cell::Timestamp(epoch_micros),
+                TypeOid::Timestamptz => Cell::Timestamptz(epoch_micros),
+                _ => Cell::Date(epoch_micros),
+            })
+        }
+    }
+}
+
+// gviz's time-of-day array has no year/month/day, so it can't become a Cell::Date/Timestamp
+// (both need a full calendar date) and this crate's `types::TypeOid` has no bare time-of-day
+// variant to target instead. Format it as a zero-padded `HH:MM:SS.mmm` string and require the
+// foreign table to declare this column `text` - there's nowhere else for it to go.
+fn parse_gviz_time_array(arr: &[JsonValue]) -> Option<Cell> {
+    let h = arr.first()?.as_i64()?;
+    let m = arr.get(1)?.as_i64()?;
+    let s = arr.get(2)?.as_i64()?;
+    let ms = arr.get(3).and_then(|v| v.as_i64()).unwrap_or(0);
+    Some(Cell::String(format!("{:02}:{:02}:{:02}.{:03}", h, m, s, ms)))
+}
+
+// convert a spreadsheet column letter (A, B, ..., Z, AA, AB, ...) to a zero-based index
+fn col_letter_to_index(letter: &str) -> Option<usize> {
+    if letter.is_empty() || !letter.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut idx: usize = 0;
+    for c in letter.chars() {
+        idx = idx * 26 + (c.to_ascii_uppercase() as usize - 'A' as usize + 1);
+    }
+    Some(idx - 1)
+}
+
+// parse the `columns` table option, e.g. "A:price:f64,B:active:bool,C:created:timestamp_iso",
+// resolving each source reference against the sheet's header row first so labels can be used,
+// falling back to a plain column letter for sheets without headers
+fn parse_column_mappings(
+    spec: &str,
+    headers: &[String],
+) -> Result<HashMap<String, ColumnMapping>, FdwError> {
+    let mut mappings = HashMap::new();
+    for entry in spec.split(',') {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = entry.splitn(3, ':').collect();
+        let (src_ref, tgt_name, col_type) = match parts[..] {
+            [a, b, c] => (a, b, c),
+            _ => return Err(format!("invalid columns mapping entry: '{}'", entry)),
+        };
+        let src_idx = headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(src_ref))
+            .or_else(|| col_letter_to_index(src_ref))
+            .ok_or_else(|| format!("cannot resolve source column '{}'", src_ref))?;
+        mappings.insert(
+            tgt_name.to_owned(),
+            ColumnMapping {
+                src_idx,
+                col_type: col_type.to_owned(),
+            },
+        );
+    }
+    Ok(mappings)
+}
 
-        // Debugging output to check the parsed values
-        println!("Parsed date: {}-{:02}-{:02}", year, month, day);
+// convert a raw gviz cell (the `{"v":...,"f":...}` object, or `null` for an empty cell) to
+// the Postgres cell type named by a `columns` mapping entry. `json`/`json:/ptr|/ptr|...` read
+// the cell object itself rather than unwrapping `/v` first - see `cell_from_json_mapped`.
+fn cell_from_mapped(src_cell: &JsonValue, col_type: &str) -> Option<Cell> {
+    if col_type == "json" {
+        return cell_from_json_mapped(src_cell, "");
+    }
+    if let Some(pointers) = col_type.strip_prefix("json:") {
+        return cell_from_json_mapped(src_cell, pointers);
+    }
 
-        // Safely format the extracted components
-        let formatted_date = format!("{:04}-{:02}-{:02}", year, month, day);
+    let src = src_cell.pointer("/v")?;
+    match col_type {
+        "i64" => src.as_f64().map(|v| Cell::I64(v as _)),
+        "f64" => src.as_f64().map(Cell::F64),
+        "numeric" => src.as_f64().map(Cell::Numeric),
+        "bool" => src.as_bool().map(Cell::Bool),
+        "string" => src
+            .as_str()
+            .map(|v| Cell::String(v.to_owned()))
+            .or_else(|| src.as_array().and_then(|a| parse_gviz_time_array(a))),
+        "date" => parse_gviz_temporal(src, TypeOid::Date),
+        "timestamp" => parse_timestamp_value(src)
+            .map(Cell::Timestamp)
+            .or_else(|| parse_gviz_temporal(src, TypeOid::Timestamp)),
+        "timestamptz" => parse_timestamp_value(src)
+            .map(Cell::Timestamptz)
+            .or_else(|| parse_gviz_temporal(src, TypeOid::Timestamptz)),
+        "timestamp_iso" => src.as_str().and_then(parse_iso_timestamp).map(Cell::Timestamp),
+        "timestamptz_iso" => src.as_str().and_then(parse_iso_timestamp).map(Cell::Timestamptz),
+        _ => None,
+    }
+}
 
-        // Attempt to convert the formatted string to a PostgreSQL-compatible timestamp
-        match time::parse_from_str(&formatted_date, "%Y-%m-%d") {
-            Ok(epoch_microseconds) => Some(Cell::Date(epoch_microseconds)),
-            Err(e) => {
-                eprintln!("Failed to parse date '{}': {}", formatted_date, e);
-                None
+// build a `Cell::Json` from a gviz cell object: with no pointer spec, serialize the whole
+// `{"v":...,"f":...}` cell (preserving Google's formatted string alongside the raw value);
+// given a `|`-separated list of pointers (e.g. "/v|/f"; `,` already separates `columns`
+// entries), pull just those into an object keyed by the pointer without its leading `/`
+fn cell_from_json_mapped(src_cell: &JsonValue, pointers: &str) -> Option<Cell> {
+    if src_cell.is_null() {
+        return None;
+    }
+    let value = if pointers.is_empty() {
+        src_cell.clone()
+    } else {
+        let mut obj = serde_json::Map::new();
+        for ptr in pointers.split('|') {
+            let ptr = ptr.trim();
+            if ptr.is_empty() {
+                continue;
             }
+            let key = ptr.trim_start_matches('/').to_owned();
+            obj.insert(key, src_cell.pointer(ptr).cloned().unwrap_or(JsonValue::Null));
+        }
+        JsonValue::Object(obj)
+    };
+    Some(Cell::Json(value.to_string()))
+}
+
+// parse either an epoch-milliseconds string/number or an ISO-8601 string into epoch microseconds
+fn parse_timestamp_value(src: &JsonValue) -> Option<i64> {
+    if let Some(s) = src.as_str() {
+        if let Ok(millis) = s.parse::<f64>() {
+            return Some((millis * 1000.0) as i64);
         }
+        return parse_iso_timestamp(s);
+    }
+    src.as_f64().map(|millis| (millis * 1000.0) as i64)
+}
+
+fn parse_iso_timestamp(s: &str) -> Option<i64> {
+    time::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%z")
+        .or_else(|_| time::parse_from_str(s, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+}
+
+// convert a zero-based index to a spreadsheet column letter (0 -> A, 25 -> Z, 26 -> AA, ...)
+fn index_to_col_letter(idx: usize) -> String {
+    let mut n = idx + 1;
+    let mut letters = Vec::new();
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        letters.push((b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+    letters.iter().rev().collect()
+}
+
+// names declared as the mapping target (2nd field) of a `columns` option spec, without
+// needing the header row to resolve them - used to keep pushdown honest before the sheet
+// has been fetched
+fn mapped_column_names(spec: &str) -> Vec<String> {
+    spec.split(',')
+        .filter_map(|entry| entry.trim().splitn(3, ':').nth(1))
+        .map(|s| s.to_owned())
+        .collect()
+}
+
+// convert a target cell back to the JSON representation the Sheets API expects for a write
+fn cell_to_json(cell: &Cell) -> JsonValue {
+    match cell {
+        Cell::I64(v) => JsonValue::from(*v),
+        Cell::F64(v) => JsonValue::from(*v),
+        Cell::Numeric(v) => JsonValue::from(*v),
+        Cell::Bool(v) => JsonValue::from(*v),
+        Cell::String(v) => JsonValue::String(v.clone()),
+        Cell::Date(v) => JsonValue::String(format_epoch_micros(*v, true)),
+        Cell::Timestamp(v) | Cell::Timestamptz(v) => JsonValue::String(format_epoch_micros(*v, false)),
+        _ => JsonValue::Null,
+    }
+}
+
+// render epoch microseconds as the `YYYY-MM-DD` (date_only) or `YYYY-MM-DDTHH:MM:SS` string
+// the Sheets API expects in a RAW-input cell, so a written-back date/timestamp round-trips
+// as an actual date rather than a 16-digit micros integer
+fn format_epoch_micros(micros: i64, date_only: bool) -> String {
+    let total_secs = micros.div_euclid(1_000_000);
+    let days = total_secs.div_euclid(86_400);
+    let secs_of_day = total_secs.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    if date_only {
+        format!("{:04}-{:02}-{:02}", year, month, day)
     } else {
-        eprintln!("Input did not match expected format: {}", src);
-        None
+        let hour = secs_of_day / 3600;
+        let minute = (secs_of_day % 3600) / 60;
+        let second = secs_of_day % 60;
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year, month, day, hour, minute, second
+        )
+    }
+}
+
+// Howard Hinnant's civil_from_days: days since the Unix epoch -> proleptic-Gregorian
+// (year, month, day). ref: http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    let year = if m <= 2 { y + 1 } else { y };
+    (year, m, d)
+}
+
+// lay out an inserted row's cells at their bound sheet column index, following the same
+// `columns` mapping (or positional fallback) used when reading the row; unbound columns are
+// left `null`, which is safe here because `insert` only ever appends a brand-new row
+fn row_to_values(col_mappings: &HashMap<String, ColumnMapping>, ctx: &Context, row: &Row) -> Vec<JsonValue> {
+    let cols = ctx.get_columns();
+    let sheet_idx = |name: &str, num: u32| {
+        col_mappings
+            .get(name)
+            .map(|m| m.src_idx)
+            .unwrap_or(num as usize - 1)
+    };
+
+    let width = cols
+        .iter()
+        .map(|c| sheet_idx(&c.name(), c.num()) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut values = vec![JsonValue::Null; width];
+    for col in &cols {
+        if let Some(cell) = row.cell(col.num() as usize - 1) {
+            values[sheet_idx(&col.name(), col.num())] = cell_to_json(&cell);
+        }
+    }
+    values
+}
+
+// same per-column binding as `row_to_values`, but returns only the cells the foreign table
+// actually exposes, each paired with its sheet column index; used for UPDATE so a column the
+// table doesn't bind is left untouched in the live sheet instead of being blanked out
+fn row_to_update_cells(
+    col_mappings: &HashMap<String, ColumnMapping>,
+    ctx: &Context,
+    row: &Row,
+) -> Vec<(usize, JsonValue)> {
+    let sheet_idx = |name: &str, num: u32| {
+        col_mappings
+            .get(name)
+            .map(|m| m.src_idx)
+            .unwrap_or(num as usize - 1)
+    };
+
+    ctx.get_columns()
+        .iter()
+        .filter_map(|col| {
+            row.cell(col.num() as usize - 1)
+                .map(|cell| (sheet_idx(&col.name(), col.num()), cell_to_json(&cell)))
+        })
+        .collect()
+}
+
+fn cell_to_tq_literal(cell: &Cell) -> Option<String> {
+    match cell {
+        Cell::I64(v) => Some(v.to_string()),
+        Cell::F64(v) => Some(v.to_string()),
+        Cell::Numeric(v) => Some(v.to_string()),
+        Cell::Bool(v) => Some(v.to_string()),
+        Cell::String(v) => Some(format!("'{}'", v.replace('\'', "''"))),
+        _ => None,
+    }
+}
+
+fn tq_operator(op: &str) -> Option<&'static str> {
+    match op {
+        "=" => Some("="),
+        "<>" => Some("!="),
+        "<" => Some("<"),
+        "<=" => Some("<="),
+        ">" => Some(">"),
+        ">=" => Some(">="),
+        _ => None,
+    }
+}
+
+// try to translate a single LIKE pattern into gviz's `contains`/`starts with` predicates;
+// only plain prefix (`foo%`) and substring (`%foo%`) patterns are supported
+fn like_to_tq(letter: &str, pattern: &str) -> Option<String> {
+    if let Some(inner) = pattern.strip_prefix('%').and_then(|s| s.strip_suffix('%')) {
+        if !inner.contains('%') && !inner.contains('_') {
+            return Some(format!("{} contains '{}'", letter, inner.replace('\'', "''")));
+        }
+    }
+    if let Some(prefix) = pattern.strip_suffix('%') {
+        if !prefix.contains('%') && !prefix.contains('_') {
+            return Some(format!("{} starts with '{}'", letter, prefix.replace('\'', "''")));
+        }
+    }
+    None
+}
+
+// build the gviz `tq` query-language string for the quals/sorts/limit Postgres pushed down,
+// returning the quals that could not be expressed so they can still be applied locally
+fn build_tq(ctx: &Context, mapped_names: &[String]) -> (String, Vec<LocalQual>) {
+    let col_nums: HashMap<String, u32> = ctx
+        .get_columns()
+        .iter()
+        .map(|c| (c.name(), c.num()))
+        .collect();
+
+    let mut where_parts = Vec::new();
+    let mut local_quals = Vec::new();
+
+    for qual in ctx.get_quals() {
+        let field = qual.field();
+        let operator = qual.operator();
+        let value = qual.value();
+
+        let resolved = (!mapped_names.iter().any(|n| n == &field))
+            .then(|| col_nums.get(&field))
+            .flatten()
+            .map(|&num| index_to_col_letter(num as usize - 1));
+
+        let pushed = resolved.and_then(|letter| match operator.as_str() {
+            "is null" => Some(format!("{} is null", letter)),
+            "is not null" => Some(format!("{} is not null", letter)),
+            "~~" => match &value {
+                Cell::String(pat) => like_to_tq(&letter, pat),
+                _ => None,
+            },
+            op => {
+                let tq_op = tq_operator(op)?;
+                let lit = cell_to_tq_literal(&value)?;
+                Some(format!("{} {} {}", letter, tq_op, lit))
+            }
+        });
+
+        match pushed {
+            Some(clause) => where_parts.push(clause),
+            None => local_quals.push(LocalQual {
+                field,
+                operator,
+                value,
+            }),
+        }
+    }
+
+    let mut tq = String::new();
+    if !where_parts.is_empty() {
+        tq.push_str("where ");
+        tq.push_str(&where_parts.join(" and "));
+    }
+
+    // gviz only accepts a single `order by`, so every sort key has to land in one
+    // comma-joined clause rather than a clause per key; a key that can't be resolved to a
+    // sheet letter (e.g. it's bound through `columns`) breaks the pushdown for all of them,
+    // since a partial `order by` would silently reorder rows Postgres didn't ask to reorder
+    let mut sort_parts = Vec::new();
+    let mut sorts_fully_pushed = true;
+    for sort in ctx.get_sorts() {
+        let field = sort.field();
+        if mapped_names.iter().any(|n| n == &field) {
+            sorts_fully_pushed = false;
+            continue;
+        }
+        match col_nums.get(&field) {
+            Some(&num) => {
+                let letter = index_to_col_letter(num as usize - 1);
+                sort_parts.push(format!(
+                    "{} {}",
+                    letter,
+                    if sort.reversed() { "desc" } else { "asc" }
+                ));
+            }
+            None => sorts_fully_pushed = false,
+        }
+    }
+    if sorts_fully_pushed && !sort_parts.is_empty() {
+        if !tq.is_empty() {
+            tq.push(' ');
+        }
+        tq.push_str("order by ");
+        tq.push_str(&sort_parts.join(", "));
+    }
+
+    // a `limit`/`offset` pushed to gviz is applied before `iter_scan`'s local qual filtering
+    // runs, so it must only be pushed when every qual and sort already made it into `tq` -
+    // otherwise the server-side page can come up short of matching rows
+    if local_quals.is_empty() && sorts_fully_pushed {
+        if let Some(limit) = ctx.get_limit() {
+            if !tq.is_empty() {
+                tq.push(' ');
+            }
+            tq.push_str(&format!("limit {}", limit.count()));
+            if limit.offset() > 0 {
+                tq.push_str(&format!(" offset {}", limit.offset()));
+            }
+        }
+    }
+
+    (tq, local_quals)
+}
+
+// percent-encode a string for use as a URL query parameter value
+fn url_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for b in input.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+fn is_retryable_status(status_code: u16) -> bool {
+    status_code == 429 || (500..=599).contains(&status_code)
+}
+
+// seconds from a `Retry-After` header, converted to milliseconds
+fn retry_after_ms(resp: &http::Response) -> Option<u64> {
+    resp.headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+        .and_then(|(_, v)| v.parse::<u64>().ok())
+        .map(|secs| secs * 1000)
+}
+
+// FNV-1a hash, used to turn a request URL into per-url jitter entropy - there's no
+// Math.random()-equivalent host binding available to this wasm guest
+fn fnv1a_hash(s: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in s.as_bytes() {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+// exponential backoff with jitter: base_ms doubles per attempt up to cap_ms, and the delay
+// returned is half that capped value plus a random amount up to the other half, so it never
+// exceeds cap_ms; the random part is derived from `seed`, so callers passing the same seed
+// for the same attempt compute the identical delay
+fn backoff_delay_ms(base_ms: u64, attempt: u32, cap_ms: u64, seed: u64) -> u64 {
+    let capped = base_ms.saturating_mul(1u64 << attempt.min(16)).min(cap_ms);
+    let mut seed = capped
+        .wrapping_add(attempt as u64)
+        .wrapping_mul(2654435761)
+        ^ seed;
+    seed ^= seed >> 13;
+    seed ^= seed << 7;
+    seed ^= seed >> 17;
+    let half = capped / 2;
+    half + seed % (half + 1)
+}
+
+// issue a request via `call`, retrying transient failures (429, 5xx, or a transport error)
+// with exponential backoff and jitter, honoring `Retry-After` when the server sends one
+fn http_with_retry(
+    url: &str,
+    max_retries: u32,
+    base_delay_ms: u64,
+    mut call: impl FnMut() -> Result<http::Response, FdwError>,
+) -> Result<http::Response, FdwError> {
+    let mut attempt = 0;
+    // the seed is the url hash with this local's address folded in; inside the wasm guest's
+    // linear memory that address is laid out the same way in every instance, so two
+    // instances retrying the same url along the same call path get the same seed
+    let instance_seed = fnv1a_hash(url) ^ (&attempt as *const u32 as u64);
+    loop {
+        match call() {
+            Ok(resp) if is_retryable_status(resp.status_code) && attempt < max_retries => {
+                let delay = retry_after_ms(&resp)
+                    .unwrap_or_else(|| backoff_delay_ms(base_delay_ms, attempt, 10_000, instance_seed));
+                utils::report_info(&format!(
+                    "got HTTP {} from {}, retrying in {}ms (attempt {}/{})",
+                    resp.status_code, url, delay, attempt + 1, max_retries
+                ));
+                time::sleep(delay);
+                attempt += 1;
+            }
+            Ok(resp) => return Ok(resp),
+            Err(e) if attempt < max_retries => {
+                let delay = backoff_delay_ms(base_delay_ms, attempt, 10_000, instance_seed);
+                utils::report_info(&format!(
+                    "request to {} failed ({}), retrying in {}ms (attempt {}/{})",
+                    url, e, delay, attempt + 1, max_retries
+                ));
+                time::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
     }
 }
 
+// check whether a source row satisfies a qual that couldn't be pushed down to `tq`
+fn local_qual_matches(src_row: &JsonValue, col_nums: &HashMap<String, u32>, qual: &LocalQual) -> bool {
+    let Some(&num) = col_nums.get(&qual.field) else {
+        return true;
+    };
+    let src = src_row.pointer(&format!("/c/{}/v", num as usize - 1));
+
+    match qual.operator.as_str() {
+        "is null" => src.map(|v| v.is_null()).unwrap_or(true),
+        "is not null" => src.map(|v| !v.is_null()).unwrap_or(false),
+        op => {
+            let Some(src) = src else {
+                return false;
+            };
+            if let Some(b) = cell_as_f64(&qual.value) {
+                let Some(a) = src.as_f64() else {
+                    return true;
+                };
+                return match op {
+                    "=" => a == b,
+                    "<>" => a != b,
+                    "<" => a < b,
+                    "<=" => a <= b,
+                    ">" => a > b,
+                    ">=" => a >= b,
+                    _ => true,
+                };
+            }
+            if let (Some(a), Cell::String(b)) = (src.as_str(), &qual.value) {
+                return match op {
+                    "=" => a == b,
+                    "<>" => a != b,
+                    "~~" => like_matches(a, b),
+                    _ => true,
+                };
+            }
+            true
+        }
+    }
+}
+
+// minimal SQL LIKE matcher (`%` = any run of characters, `_` = any single character) used
+// to apply quals locally when they couldn't be expressed as a gviz `tq` predicate
+fn like_matches(value: &str, pattern: &str) -> bool {
+    fn matches(value: &[char], pattern: &[char]) -> bool {
+        match pattern.split_first() {
+            None => value.is_empty(),
+            Some((&'%', rest)) => {
+                (0..=value.len()).any(|i| matches(&value[i..], rest))
+            }
+            Some((&'_', rest)) => !value.is_empty() && matches(&value[1..], rest),
+            Some((c, rest)) => value.first() == Some(c) && matches(&value[1..], rest),
+        }
+    }
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    matches(&value, &pattern)
+}
+
+fn cell_as_f64(cell: &Cell) -> Option<f64> {
+    match cell {
+        Cell::I64(v) => Some(*v as f64),
+        Cell::F64(v) => Some(*v),
+        Cell::Numeric(v) => Some(*v),
+        _ => None,
+    }
+}
 
 // pointer for the static FDW instance
 static mut INSTANCE: *mut ExampleFdw = std::ptr::null_mut::<ExampleFdw>();
@@ -71,6 +701,117 @@ impl ExampleFdw {
     fn this_mut() -> &'static mut Self {
         unsafe { &mut (*INSTANCE) }
     }
+
+    // the `Authorization` header to send, if an OAuth bearer token was configured; Google
+    // rejects an `api_key` sent this way, so that credential is never used here - see
+    // `with_api_key`
+    fn auth_header(&self) -> Option<(String, String)> {
+        let token = self.bearer_token.as_ref()?;
+        Some(("authorization".to_owned(), format!("Bearer {}", token)))
+    }
+
+    // append the configured `api_key` as a `key=` query param, Google's accepted form for
+    // unauthenticated access to public data
+    fn with_api_key(&self, url: &str) -> String {
+        match &self.api_key {
+            Some(key) => format!(
+                "{}{}key={}",
+                url,
+                if url.contains('?') { "&" } else { "?" },
+                url_encode(key)
+            ),
+            None => url.to_owned(),
+        }
+    }
+
+    // POST a write-back request to the Sheets API, with credentials applied. `retryable`
+    // must be false for any request that isn't safe to repeat if Google committed it but the
+    // response was lost - e.g. `values:append`, which would otherwise duplicate a row.
+    fn send_write_request(&self, url: &str, body: String, retryable: bool) -> FdwResult {
+        let url = self.with_api_key(url);
+        let mut headers: Vec<(String, String)> = vec![
+            ("user-agent".to_owned(), "Sheets FDW".to_owned()),
+            ("content-type".to_owned(), "application/json".to_owned()),
+        ];
+        if let Some(auth) = self.auth_header() {
+            headers.push(auth);
+        }
+        if let Some(cookie) = &self.cookie {
+            headers.push(("cookie".to_owned(), cookie.clone()));
+        }
+
+        let req = http::Request {
+            method: http::Method::Post,
+            url: url.clone(),
+            headers,
+            body,
+        };
+        let resp = if retryable {
+            http_with_retry(&url, self.max_retries, self.retry_base_delay_ms, || {
+                http::post(&req)
+            })?
+        } else {
+            http::post(&req)?
+        };
+        if !(200..300).contains(&resp.status_code) {
+            return Err(format!("Sheets API request to {} failed: {}", url, resp.body));
+        }
+        Ok(())
+    }
+
+    // GET the gviz endpoint for `sheet_id` with an optional `tq` query, with credentials and
+    // retry applied (a GET is always safe to retry), returning the parsed JSON response
+    fn fetch_gviz(&self, sheet_id: &str, tq: &str) -> Result<JsonValue, FdwError> {
+        let mut url = format!("{}/{}/gviz/tq?tqx=out:json", self.base_url, sheet_id);
+        if !tq.is_empty() {
+            url.push_str(&format!("&tq={}", url_encode(tq)));
+        }
+        let url = self.with_api_key(&url);
+
+        let mut headers: Vec<(String, String)> = vec![
+            ("user-agent".to_owned(), "Sheets FDW".to_owned()),
+            // header to make JSON response more cleaner
+            ("x-datasource-auth".to_owned(), "true".to_owned()),
+        ];
+        if let Some(auth) = self.auth_header() {
+            headers.push(auth);
+        }
+        if let Some(cookie) = &self.cookie {
+            headers.push(("cookie".to_owned(), cookie.clone()));
+        }
+
+        let req = http::Request {
+            method: http::Method::Get,
+            url: url.clone(),
+            headers,
+            body: String::default(),
+        };
+        let resp = http_with_retry(&url, self.max_retries, self.retry_base_delay_ms, || {
+            http::get(&req)
+        })?;
+        // remove invalid prefix from response to make a valid JSON string
+        let body = resp.body.strip_prefix(")]}'\n").ok_or("invalid response")?;
+        serde_json::from_str(body).map_err(|e| e.to_string())
+    }
+
+    // pull the sheet's header row labels (`/table/cols/*/label`) out of a parsed gviz
+    // response, so the `columns` option can bind by label instead of just position
+    fn col_labels_from_gviz(resp_json: &JsonValue) -> Vec<String> {
+        resp_json
+            .pointer("/table/cols")
+            .and_then(|v| v.as_array())
+            .map(|cols| {
+                cols.iter()
+                    .map(|c| {
+                        c.pointer("/label")
+                            .and_then(|l| l.as_str())
+                            .unwrap_or_default()
+                            .to_owned()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl Guest for ExampleFdw {
@@ -88,35 +829,47 @@ impl Guest for ExampleFdw {
         let opts = ctx.get_options(OptionsType::Server);
         this.base_url = opts.require_or("base_url", "https://docs.google.com/spreadsheets/d");
 
+        // credentials for private or OAuth-protected sheets; may be given directly or as a
+        // Supabase Vault secret reference, both transparently resolved by `opts.get`
+        this.api_key = opts.get("api_key");
+        this.bearer_token = opts.get("bearer_token");
+        this.cookie = opts.get("cookie");
+
+        // retry tuning for transient HTTP failures
+        this.max_retries = opts
+            .require_or("max_retries", "3")
+            .parse()
+            .map_err(|_| "max_retries must be an integer".to_owned())?;
+        this.retry_base_delay_ms = opts
+            .require_or("retry_base_delay_ms", "200")
+            .parse()
+            .map_err(|_| "retry_base_delay_ms must be an integer".to_owned())?;
+
         Ok(())
     }
 
     fn begin_scan(ctx: &Context) -> FdwResult {
         let this = Self::this_mut();
 
-        // get sheet id from foreign table options and make the request URL
+        // get sheet id from foreign table options
         let opts = ctx.get_options(OptionsType::Table);
         let sheet_id = opts.require("sheet_id")?;
-        let url = format!("{}/{}/gviz/tq?tqx=out:json", this.base_url, sheet_id);
 
-        // make up request headers
-        let headers: Vec<(String, String)> = vec![
-            ("user-agent".to_owned(), "Sheets FDW".to_owned()),
-            // header to make JSON response more cleaner
-            ("x-datasource-auth".to_owned(), "true".to_owned()),
-        ];
+        // columns bound through the `columns` option aren't resolvable to a sheet letter
+        // until the header row comes back, so they're excluded from pushdown and filtered
+        // locally instead
+        let mapped_names = opts
+            .get("columns")
+            .map(|spec| mapped_column_names(&spec))
+            .unwrap_or_default();
 
-        // make a request to Google API and parse response as JSON
-        let req = http::Request {
-            method: http::Method::Get,
-            url,
-            headers,
-            body: String::default(),
-        };
-        let resp = http::get(&req)?;
-        // remove invalid prefix from response to make a valid JSON string
-        let body = resp.body.strip_prefix(")]}'\n").ok_or("invalid response")?;
-        let resp_json: JsonValue = serde_json::from_str(body).map_err(|e| e.to_string())?;
+        // translate pushed-down quals/sorts/limit into the gviz `tq` query language so
+        // filtering and paging happen server-side; anything that can't be expressed is
+        // kept for local filtering in iter_scan
+        let (tq, local_quals) = build_tq(ctx, &mapped_names);
+        this.local_quals = local_quals;
+
+        let resp_json = this.fetch_gviz(&sheet_id, &tq)?;
 
         // extract source rows from response
         this.src_rows = resp_json
@@ -124,6 +877,17 @@ impl Guest for ExampleFdw {
             .ok_or("cannot get rows from response")
             .map(|v| v.as_array().unwrap().to_owned())?;
 
+        // parse the declarative `columns` mapping if the foreign table declares one, e.g.
+        // `columns 'A:price:f64,B:active:bool,C:created:timestamp_iso'`, resolving the header
+        // row so it can bind by label, not just position
+        this.col_mappings = match opts.get("columns") {
+            Some(spec) => {
+                let col_labels = Self::col_labels_from_gviz(&resp_json);
+                parse_column_mappings(&spec, &col_labels)?
+            }
+            None => HashMap::new(),
+        };
+
         // output a Postgres INFO to user (visible in psql), also useful for debugging
         utils::report_info(&format!(
             "We got response array length: {}",
@@ -136,6 +900,32 @@ impl Guest for ExampleFdw {
     fn iter_scan(ctx: &Context, row: &Row) -> Result<Option<u32>, FdwError> {
         let this = Self::this_mut();
 
+        // skip any rows that fail quals the `tq` pushdown couldn't express
+        if !this.local_quals.is_empty() {
+            // 1-based source position per column: the `columns` binding if declared, else
+            // the column's own position
+            let col_nums: HashMap<String, u32> = ctx
+                .get_columns()
+                .iter()
+                .map(|c| {
+                    let num = this
+                        .col_mappings
+                        .get(&c.name())
+                        .map(|m| m.src_idx as u32 + 1)
+                        .unwrap_or(c.num());
+                    (c.name(), num)
+                })
+                .collect();
+            while this.src_idx < this.src_rows.len()
+                && !this
+                    .local_quals
+                    .iter()
+                    .all(|q| local_qual_matches(&this.src_rows[this.src_idx], &col_nums, q))
+            {
+                this.src_idx += 1;
+            }
+        }
+
         // if all source rows are consumed, stop data scan
         if this.src_idx >= this.src_rows.len() {
             return Ok(None);
@@ -156,20 +946,47 @@ impl Guest for ExampleFdw {
         // loop through each target column, map source cell to target cell
         for tgt_col in ctx.get_columns() {
             let (tgt_col_num, tgt_col_name) = (tgt_col.num(), tgt_col.name());
-            if let Some(src) = src_row.pointer(&format!("/c/{}/v", tgt_col_num - 1)) {
-                // we only support I64 and String cell types here, add more type
-                // conversions if you need
-                let cell = match tgt_col.type_oid() {
-                    TypeOid::I64 => src.as_f64().map(|v| Cell::I64(v as _)),
-                    TypeOid::String => src.as_str().map(|v| Cell::String(v.to_owned())),
-                    TypeOid::Date => parse_date_from_interface(src.as_str().unwrap_or("")),
-
-                    _ => {
-                        return Err(format!(
-                            "column {} data type is not supported",
-                            tgt_col_name
-                        ));
+
+            // a declared mapping binds by resolved source index and carries its own type,
+            // otherwise we fall back to positional binding driven by the column's type_oid
+            let mapping = this.col_mappings.get(&tgt_col_name).cloned();
+            let src_idx = mapping
+                .as_ref()
+                .map(|m| m.src_idx)
+                .unwrap_or(tgt_col_num as usize - 1);
+
+            if let Some(src_cell) = src_row.pointer(&format!("/c/{}", src_idx)) {
+                let cell = if let Some(mapping) = &mapping {
+                    cell_from_mapped(src_cell, &mapping.col_type)
+                } else if matches!(tgt_col.type_oid(), TypeOid::Json) {
+                    cell_from_json_mapped(src_cell, "")
+                } else if let Some(src) = src_cell.pointer("/v") {
+                    match tgt_col.type_oid() {
+                        TypeOid::I64 => src.as_f64().map(|v| Cell::I64(v as _)),
+                        TypeOid::F64 => src.as_f64().map(Cell::F64),
+                        TypeOid::Numeric => src.as_f64().map(Cell::Numeric),
+                        TypeOid::Bool => src.as_bool().map(Cell::Bool),
+                        TypeOid::String => src
+                            .as_str()
+                            .map(|v| Cell::String(v.to_owned()))
+                            .or_else(|| src.as_array().and_then(|a| parse_gviz_time_array(a))),
+                        TypeOid::Date => parse_gviz_temporal(src, TypeOid::Date),
+                        TypeOid::Timestamp => parse_timestamp_value(src)
+                            .map(Cell::Timestamp)
+                            .or_else(|| parse_gviz_temporal(src, TypeOid::Timestamp)),
+                        TypeOid::Timestamptz => parse_timestamp_value(src)
+                            .map(Cell::Timestamptz)
+                            .or_else(|| parse_gviz_temporal(src, TypeOid::Timestamptz)),
+
+                        _ => {
+                            return Err(format!(
+                                "column {} data type is not supported",
+                                tgt_col_name
+                            ));
+                        }
                     }
+                } else {
+                    None
                 };
 
                 // push the cell to target row
@@ -196,23 +1013,137 @@ impl Guest for ExampleFdw {
         Ok(())
     }
 
-    fn begin_modify(_ctx: &Context) -> FdwResult {
-        Err("modify on foreign table is not supported".to_owned())
+    fn begin_modify(ctx: &Context) -> FdwResult {
+        let this = Self::this_mut();
+
+        let opts = ctx.get_options(OptionsType::Table);
+        this.api_base_url = opts.require_or("api_base_url", "https://sheets.googleapis.com/v4/spreadsheets");
+        this.modify_sheet_id = opts.require("sheet_id")?;
+        this.modify_range = opts.require_or("range", "Sheet1");
+        this.modify_sheet_gid = opts
+            .require_or("sheet_gid", "0")
+            .parse()
+            .map_err(|_| "sheet_gid must be an integer".to_owned())?;
+
+        // resolve the same declarative `columns` mapping `begin_scan` builds, fetching the
+        // header row ourselves - a write-only statement (e.g. a bare INSERT) never runs a
+        // scan, so `col_mappings` can't be left to whatever `begin_scan` happened to populate
+        this.col_mappings = match opts.get("columns") {
+            Some(spec) => {
+                let resp_json = this.fetch_gviz(&this.modify_sheet_id.clone(), "select * limit 0")?;
+                let col_labels = Self::col_labels_from_gviz(&resp_json);
+                parse_column_mappings(&spec, &col_labels)?
+            }
+            None => HashMap::new(),
+        };
+
+        this.pending_inserts.clear();
+        this.pending_updates.clear();
+        this.pending_deletes.clear();
+
+        Ok(())
     }
 
-    fn insert(_ctx: &Context, _row: &Row) -> FdwResult {
+    fn insert(ctx: &Context, row: &Row) -> FdwResult {
+        let this = Self::this_mut();
+        let values = row_to_values(&this.col_mappings, ctx, row);
+        this.pending_inserts.push(values);
         Ok(())
     }
 
-    fn update(_ctx: &Context, _rowid: Cell, _row: &Row) -> FdwResult {
+    fn update(ctx: &Context, rowid: Cell, row: &Row) -> FdwResult {
+        let this = Self::this_mut();
+        let row_num = cell_as_f64(&rowid).ok_or("rowid_column must be numeric")? as i64;
+        let cells = row_to_update_cells(&this.col_mappings, ctx, row);
+        this.pending_updates.push((row_num, cells));
         Ok(())
     }
 
-    fn delete(_ctx: &Context, _rowid: Cell) -> FdwResult {
+    fn delete(_ctx: &Context, rowid: Cell) -> FdwResult {
+        let this = Self::this_mut();
+        let row_num = cell_as_f64(&rowid).ok_or("rowid_column must be numeric")? as i64;
+        this.pending_deletes.push(row_num);
         Ok(())
     }
 
     fn end_modify(_ctx: &Context) -> FdwResult {
+        let this = Self::this_mut();
+
+        if !this.pending_inserts.is_empty() {
+            let url = format!(
+                "{}/{}/values/{}:append?valueInputOption=RAW",
+                this.api_base_url,
+                this.modify_sheet_id,
+                url_encode(&this.modify_range),
+            );
+            let body = serde_json::json!({ "values": this.pending_inserts }).to_string();
+            // values:append is not idempotent - a lost response after Google committed the
+            // append would duplicate the row on retry, so this request is sent at most once
+            this.send_write_request(&url, body, false)?;
+            this.pending_inserts.clear();
+        }
+
+        if !this.pending_updates.is_empty() {
+            // one values:batchUpdate data entry per bound cell, each addressed by its own
+            // sheet column letter, so a column the foreign table doesn't expose is left
+            // exactly as it was instead of being blanked out by a null-padded full row
+            let modify_range = this.modify_range.clone();
+            let data: Vec<JsonValue> = this
+                .pending_updates
+                .iter()
+                .flat_map(|(row_num, cells)| {
+                    let modify_range = &modify_range;
+                    cells.iter().map(move |(sheet_idx, value)| {
+                        let cell_ref = format!(
+                            "{}{}",
+                            index_to_col_letter(*sheet_idx),
+                            row_num + HEADER_OFFSET
+                        );
+                        serde_json::json!({
+                            "range": format!("{}!{}", modify_range, cell_ref),
+                            "values": [[value]],
+                        })
+                    })
+                })
+                .collect();
+            let url = format!("{}/{}/values:batchUpdate", this.api_base_url, this.modify_sheet_id);
+            let body = serde_json::json!({ "valueInputOption": "RAW", "data": data }).to_string();
+            // setting explicit cell values is idempotent - replaying it after a lost response
+            // converges to the same state, so it's safe to retry
+            this.send_write_request(&url, body, true)?;
+            this.pending_updates.clear();
+        }
+
+        if !this.pending_deletes.is_empty() {
+            // delete highest row numbers first so earlier deleteDimension requests don't
+            // shift the row indices later requests in the same batch still target
+            let mut rows = this.pending_deletes.clone();
+            rows.sort_unstable_by(|a, b| b.cmp(a));
+            let requests: Vec<JsonValue> = rows
+                .iter()
+                .map(|row_num| {
+                    let start = row_num + HEADER_OFFSET - 1;
+                    serde_json::json!({
+                        "deleteDimension": {
+                            "range": {
+                                "sheetId": this.modify_sheet_gid,
+                                "dimension": "ROWS",
+                                "startIndex": start,
+                                "endIndex": start + 1,
+                            }
+                        }
+                    })
+                })
+                .collect();
+            let url = format!("{}/{}:batchUpdate", this.api_base_url, this.modify_sheet_id);
+            let body = serde_json::json!({ "requests": requests }).to_string();
+            // deleteDimension targets rows by index - if the first attempt's response is lost
+            // after Google applies it, a retry would delete whatever row has since shifted
+            // into that index, so this request is sent at most once
+            this.send_write_request(&url, body, false)?;
+            this.pending_deletes.clear();
+        }
+
         Ok(())
     }
 }